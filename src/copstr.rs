@@ -15,14 +15,83 @@
 //! functions guarantee that the contents are valid UTF-8 and return
 //! an error if they are not.
 
-use std::convert::TryFrom;
-use std::error;
-use std::fmt;
-use std::hash;
-use std::iter;
-use std::ops;
-use std::str;
-use std::str::FromStr;
+use core::cmp;
+use core::convert::TryFrom;
+use core::error;
+use core::fmt;
+use core::hash;
+use core::iter;
+use core::ops;
+use core::str;
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+/// Validates that `bytes` is well-formed UTF-8, panicking otherwise.
+///
+/// This runs in const context, turning invalid input to
+/// [`Str::new_const_checked_u8`] into a build-time error instead of
+/// the UB risked by the unchecked `new_const*` constructors.
+const fn validate_utf8(bytes: &[u8]) {
+    let len = bytes.len();
+    let mut i = 0;
+    while i < len {
+        let b = bytes[i];
+        let seqlen = match b {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => panic!("invalid UTF-8: bad leading byte"),
+        };
+        assert!(i + seqlen <= len, "invalid UTF-8: truncated sequence");
+        if seqlen >= 2 {
+            let b1 = bytes[i + 1];
+            assert!(
+                matches!(b1, 0x80..=0xBF),
+                "invalid UTF-8: bad continuation byte"
+            );
+            if seqlen == 2 {
+                assert!(
+                    !matches!(b, 0xC0..=0xC1),
+                    "invalid UTF-8: overlong sequence"
+                );
+            } else if seqlen == 3 {
+                assert!(
+                    !(b == 0xE0 && b1 < 0xA0),
+                    "invalid UTF-8: overlong sequence"
+                );
+                assert!(
+                    !(b == 0xED && b1 >= 0xA0),
+                    "invalid UTF-8: surrogate code point"
+                );
+            } else if seqlen == 4 {
+                assert!(
+                    !(b == 0xF0 && b1 < 0x90),
+                    "invalid UTF-8: overlong sequence"
+                );
+                assert!(
+                    !(b == 0xF4 && b1 > 0x8F),
+                    "invalid UTF-8: out-of-range code point"
+                );
+            }
+        }
+        if seqlen >= 3 {
+            assert!(
+                matches!(bytes[i + 2], 0x80..=0xBF),
+                "invalid UTF-8: bad continuation byte"
+            );
+        }
+        if seqlen == 4 {
+            assert!(
+                matches!(bytes[i + 3], 0x80..=0xBF),
+                "invalid UTF-8: bad continuation byte"
+            );
+        }
+        i += seqlen;
+    }
+}
 
 /// Copy String type
 ///
@@ -99,6 +168,37 @@ impl<const SIZE: usize> Str<SIZE> {
         Self::new_const_trunc_u8(bytes)
     }
 
+    /// Create a new [`Str`] in const context, with the contents specified by the
+    /// provided string, validating that it is well-formed UTF-8.
+    ///
+    /// Unlike [`new_const`](Self::new_const), this function validates
+    /// the input and has no UB risk: it panics at compile time if the
+    /// string isn't well-formed UTF-8, on top of the existing panic if
+    /// it doesn't fit in SIZE bytes:
+    /// ```compile_fail
+    /// const TEST: copstr::Str<3> = copstr::Str::<3>::new_const_checked("test");
+    /// ```
+    pub const fn new_const_checked(string: &str) -> Self {
+        Self::new_const_checked_u8(string.as_bytes())
+    }
+
+    /// Create a new [`Str`] in const context, with the contents specified by the
+    /// provided array of `u8`, validating that it is well-formed UTF-8.
+    ///
+    /// Unlike [`new_const_u8`](Self::new_const_u8), this function
+    /// validates the input and has no UB risk: it panics at compile
+    /// time if the bytes aren't well-formed UTF-8, on top of the
+    /// existing panic if they don't fit in SIZE bytes. For instance,
+    /// the overlong 2-byte encoding of `'\0'` fits in `SIZE` but isn't
+    /// well-formed UTF-8:
+    /// ```compile_fail
+    /// const TEST: copstr::Str<2> = copstr::Str::<2>::new_const_checked_u8(&[0xC0, 0x80]);
+    /// ```
+    pub const fn new_const_checked_u8(bytes: &[u8]) -> Self {
+        validate_utf8(bytes);
+        Self::new_const_u8(bytes)
+    }
+
     /// Create a new [`Str`] in const context, with the contents specified by the
     /// provided array of `u8`, truncated to fit.
     ///
@@ -170,11 +270,137 @@ impl<const SIZE: usize> Str<SIZE> {
         }
     }
 
+    /// Appends the given string slice to the end of this `Str`, if possible.
+    pub fn push_str(&mut self, string: &str) -> Result<(), ErrorOverflow> {
+        let bytes = string.as_bytes();
+        if bytes.len() > self.capacity() - self.byte_len() {
+            Err(ErrorOverflow {})
+        } else {
+            let fromlen = self.0.split_at_mut(self.1).1;
+            let dest = fromlen.split_at_mut(bytes.len()).0;
+            dest.copy_from_slice(bytes);
+            self.1 += bytes.len();
+            Ok(())
+        }
+    }
+
+    /// Removes the last character and returns it, or `None` if empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.1 -= ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Shortens this `Str` to the given byte length.
+    ///
+    /// `new_len` must be at a UTF-8 char boundary; this function does
+    /// nothing if `new_len` is greater than or equal to the current
+    /// length.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.1 {
+            assert!(self.as_str().is_char_boundary(new_len));
+            self.1 = new_len;
+        }
+    }
+
+    /// Truncates this `Str` to zero length.
+    pub fn clear(&mut self) {
+        self.1 = 0;
+    }
+
+    /// Inserts a character at the given byte index, if possible.
+    ///
+    /// `idx` must be at a UTF-8 char boundary.
+    pub fn insert(&mut self, idx: usize, ch: char) -> Result<(), ErrorOverflow> {
+        assert!(self.as_str().is_char_boundary(idx));
+        let mut buffer = [0; 4];
+        let encoded = ch.encode_utf8(&mut buffer).as_bytes();
+        let chlen = encoded.len();
+        if chlen > self.capacity() - self.byte_len() {
+            return Err(ErrorOverflow {});
+        }
+        self.0.copy_within(idx..self.1, idx + chlen);
+        self.0[idx..idx + chlen].copy_from_slice(encoded);
+        self.1 += chlen;
+        Ok(())
+    }
+
+    /// Removes and returns the character at the given byte index.
+    ///
+    /// `idx` must be at a UTF-8 char boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds or not at a char boundary.
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = self.as_str()[idx..]
+            .chars()
+            .next()
+            .expect("idx is out of bounds");
+        let chlen = ch.len_utf8();
+        self.0.copy_within(idx + chlen..self.1, idx);
+        self.1 -= chlen;
+        ch
+    }
+
+    /// Keeps only the characters for which `f` returns `true`.
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        let mut retained = Self::default();
+        for ch in self.as_str().chars() {
+            if f(ch) {
+                // ch was already part of a string that fit in SIZE bytes,
+                // so pushing a subset of its chars can't overflow.
+                let _ = retained.push(ch);
+            }
+        }
+        *self = retained;
+    }
+
     /// Extracts a string slice containing the entire `Str`.
     pub fn as_str(&self) -> &str {
         // SAFETY: self.0 is always UTF-8
         unsafe { str::from_utf8_unchecked(&self.0[0..self.1]) }
     }
+
+    /// Returns the backing array, with unused trailing bytes zeroed.
+    ///
+    /// Combined with [`from_padded_array`](Self::from_padded_array),
+    /// this gives a fixed-width representation suitable for on-disk
+    /// records or FFI, where the contents are written/read as a plain
+    /// `[u8; SIZE]`.
+    ///
+    /// <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
+    /// <strong>WARNING:</strong> the logical length isn't encoded
+    /// anywhere in the array, so it's recovered on the way back by
+    /// trimming trailing NUL bytes. A `Str` whose content itself ends
+    /// in `'\0'` (a perfectly legal `char`) is therefore <em>not</em>
+    /// round-trippable: those trailing NULs are indistinguishable from
+    /// padding and will be lost.
+    /// </p>
+    pub fn to_padded_array(&self) -> [u8; SIZE] {
+        let mut arr = [0; SIZE];
+        arr[0..self.1].copy_from_slice(&self.0[0..self.1]);
+        arr
+    }
+
+    /// Builds a [`Str`] from a fixed-width, zero-padded byte array, as
+    /// produced by [`to_padded_array`](Self::to_padded_array).
+    ///
+    /// The logical length is found by trimming trailing NUL bytes, and
+    /// the remaining prefix is validated as UTF-8. See the warning on
+    /// [`to_padded_array`](Self::to_padded_array) about content that
+    /// itself ends in `'\0'`.
+    pub fn from_padded_array(arr: [u8; SIZE]) -> Result<Self, Error> {
+        let len = arr.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+        str::from_utf8(&arr[0..len])?;
+        Ok(Str(arr, len))
+    }
+}
+
+impl<const SIZE: usize> fmt::Write for Str<SIZE> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
 }
 
 impl<const SIZE: usize> Default for Str<SIZE> {
@@ -218,6 +444,54 @@ impl<const SIZE: usize> AsRef<[u8]> for Str<SIZE> {
     }
 }
 
+/* Serde: **********************************************************/
+
+#[cfg(feature = "serde")]
+impl<const SIZE: usize> serde::Serialize for Str<SIZE> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+struct StrVisitor<const SIZE: usize>;
+
+#[cfg(feature = "serde")]
+impl<'de, const SIZE: usize> serde::de::Visitor<'de> for StrVisitor<SIZE> {
+    type Value = Str<SIZE>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string of at most {} bytes", SIZE)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Str::new(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(v)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const SIZE: usize> serde::Deserialize<'de> for Str<SIZE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
 impl<const SIZE: usize> fmt::Display for Str<SIZE> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -246,6 +520,96 @@ impl<const SIZE: usize> PartialEq for Str<SIZE> {
 }
 impl<const SIZE: usize> Eq for Str<SIZE> {}
 
+impl<const SIZE: usize> PartialOrd for Str<SIZE> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const SIZE: usize> Ord for Str<SIZE> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+/* Cross-type comparisons with str/String: ***************************/
+
+impl<const SIZE: usize> PartialEq<str> for Str<SIZE> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const SIZE: usize> PartialEq<Str<SIZE>> for str {
+    fn eq(&self, other: &Str<SIZE>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<const SIZE: usize> PartialEq<&str> for Str<SIZE> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const SIZE: usize> PartialEq<Str<SIZE>> for &str {
+    fn eq(&self, other: &Str<SIZE>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize> PartialEq<String> for Str<SIZE> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize> PartialEq<Str<SIZE>> for String {
+    fn eq(&self, other: &Str<SIZE>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const SIZE: usize> PartialOrd<str> for Str<SIZE> {
+    fn partial_cmp(&self, other: &str) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other)
+    }
+}
+
+impl<const SIZE: usize> PartialOrd<Str<SIZE>> for str {
+    fn partial_cmp(&self, other: &Str<SIZE>) -> Option<cmp::Ordering> {
+        self.partial_cmp(other.as_str())
+    }
+}
+
+impl<const SIZE: usize> PartialOrd<&str> for Str<SIZE> {
+    fn partial_cmp(&self, other: &&str) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(*other)
+    }
+}
+
+impl<const SIZE: usize> PartialOrd<Str<SIZE>> for &str {
+    fn partial_cmp(&self, other: &Str<SIZE>) -> Option<cmp::Ordering> {
+        (*self).partial_cmp(other.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize> PartialOrd<String> for Str<SIZE> {
+    fn partial_cmp(&self, other: &String) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const SIZE: usize> PartialOrd<Str<SIZE>> for String {
+    fn partial_cmp(&self, other: &Str<SIZE>) -> Option<cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
 impl<const SIZE: usize> hash::Hash for Str<SIZE> {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         self.as_str().hash(state);
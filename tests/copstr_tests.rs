@@ -123,6 +123,99 @@ fn test_replace_trunc() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_push_str() -> Result<()> {
+    let mut no = Str::new("n")?;
+    no.push_str("ow")?;
+    assert_str(&no, "now");
+    Ok(())
+}
+
+#[test]
+fn test_push_str_err() -> Result<()> {
+    let mut basic = Str::new("bas")?;
+    assert_eq!(basic.push_str("ics").unwrap_err(), copstr::ErrorOverflow);
+    assert_str(&basic, "bas");
+    Ok(())
+}
+
+#[test]
+fn test_pop() -> Result<()> {
+    let mut basic = Str::new("basic")?;
+    assert_eq!(basic.pop(), Some('c'));
+    assert_str(&basic, "basi");
+    let mut empty = Str::default();
+    assert_eq!(empty.pop(), None);
+    Ok(())
+}
+
+#[test]
+fn test_truncate() -> Result<()> {
+    let mut basic = Str::new("basic")?;
+    basic.truncate(3);
+    assert_str(&basic, "bas");
+    basic.truncate(10);
+    assert_str(&basic, "bas");
+    Ok(())
+}
+
+#[test]
+fn test_clear() -> Result<()> {
+    let mut basic = Str::new("basic")?;
+    basic.clear();
+    assert_str(&basic, "");
+    Ok(())
+}
+
+#[test]
+fn test_insert() -> Result<()> {
+    let mut bsic = Str::new("bsic")?;
+    bsic.insert(1, 'a')?;
+    assert_str(&bsic, "basic");
+    Ok(())
+}
+
+#[test]
+fn test_insert_err() -> Result<()> {
+    let mut basic = Str::new("basic")?;
+    assert_eq!(basic.insert(0, 'x').unwrap_err(), copstr::ErrorOverflow);
+    assert_str(&basic, "basic");
+    Ok(())
+}
+
+#[test]
+fn test_remove() -> Result<()> {
+    let mut basic = Str::new("bassc")?;
+    assert_eq!(basic.remove(3), 's');
+    assert_str(&basic, "basc");
+    Ok(())
+}
+
+#[test]
+fn test_retain() -> Result<()> {
+    let mut basic = Str::new("basic")?;
+    basic.retain(|c| c != 'a');
+    assert_str(&basic, "bsic");
+    Ok(())
+}
+
+#[test]
+fn test_write() -> Result<()> {
+    use std::fmt::Write;
+    let mut s = Str::default();
+    write!(s, "{}{}", "ba", 1)?;
+    assert_str(&s, "ba1");
+    Ok(())
+}
+
+#[test]
+fn test_write_err() -> Result<()> {
+    use std::fmt::Write;
+    let mut s = Str::default();
+    assert!(write!(s, "stringification").is_err());
+    Ok(())
+}
+
 const SPARKLE_HEART: [u8; 4] = [240, 159, 146, 150];
 const INVALID_UTF8: [u8; 4] = [0, 159, 146, 150];
 
@@ -175,6 +268,43 @@ fn test_utf8_invalid_replace() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_ord() -> Result<()> {
+    let a = Str::new("abc")?;
+    let b = Str::new("abd")?;
+    assert!(a < b);
+    let mut set = std::collections::BTreeSet::<Str>::new();
+    set.insert(b);
+    set.insert(a);
+    assert_eq!(
+        set.into_iter().collect::<Vec<_>>(),
+        &[Str::new("abc")?, Str::new("abd")?]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cross_eq() -> Result<()> {
+    let basic = Str::new("basic")?;
+    assert_eq!(basic, *"basic");
+    assert_eq!(*"basic", basic);
+    assert_eq!(basic, "basic");
+    assert_eq!("basic", basic);
+    assert_eq!(basic, "basic".to_string());
+    assert_eq!("basic".to_string(), basic);
+    Ok(())
+}
+
+#[test]
+fn test_cross_ord() -> Result<()> {
+    let basic = Str::new("basic")?;
+    assert!(basic < *"basid");
+    assert!(*"basia" < basic);
+    assert!(basic < "basid");
+    assert!("basid" > basic);
+    Ok(())
+}
+
 #[test]
 fn test_hash() -> Result<()> {
     let mut set = std::collections::HashSet::<Str>::new();
@@ -198,3 +328,131 @@ fn test_const() -> Result<()> {
     assert_eq!(TEST.as_str(), "test");
     Ok(())
 }
+
+#[test]
+fn test_const_checked() -> Result<()> {
+    const TEST_U8: Str = Str::new_const_checked_u8(b"test");
+    assert_eq!(TEST_U8.as_str(), "test");
+    const TEST: Str = Str::new_const_checked("test");
+    assert_eq!(TEST.as_str(), "test");
+    const SPARKLE: copstr::Str<4> = copstr::Str::<4>::new_const_checked_u8(&SPARKLE_HEART);
+    assert_eq!(SPARKLE.as_str(), "\u{1F496}");
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_overlong() {
+    // 0xC0 0x80 is the overlong 2-byte encoding of '\0'.
+    let _ = copstr::Str::<2>::new_const_checked_u8(&[0xC0, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_3byte_overlong() {
+    // 0xE0 0x9F ... is an overlong 3-byte sequence (valid range is
+    // 0xA0..=0xBF after a 0xE0 leading byte).
+    let _ = copstr::Str::<3>::new_const_checked_u8(&[0xE0, 0x9F, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_surrogate() {
+    // 0xED 0xA0 ... encodes a surrogate code point, which isn't valid UTF-8.
+    let _ = copstr::Str::<3>::new_const_checked_u8(&[0xED, 0xA0, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_4byte_overlong() {
+    // 0xF0 0x8F ... is an overlong 4-byte sequence (valid range is
+    // 0x90..=0xBF after a 0xF0 leading byte).
+    let _ = copstr::Str::<4>::new_const_checked_u8(&[0xF0, 0x8F, 0x80, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_out_of_range() {
+    // 0xF4 0x90 ... encodes a code point beyond U+10FFFF.
+    let _ = copstr::Str::<4>::new_const_checked_u8(&[0xF4, 0x90, 0x80, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_truncated_sequence() {
+    // 0xE0 starts a 3-byte sequence, but only one byte is given.
+    let _ = copstr::Str::<3>::new_const_checked_u8(&[0xE0]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_bad_leading_byte() {
+    // 0xFF is never a valid UTF-8 leading byte.
+    let _ = copstr::Str::<3>::new_const_checked_u8(&[0xFF, 0x80, 0x80]);
+}
+
+#[test]
+#[should_panic(expected = "invalid UTF-8")]
+fn test_const_checked_bad_continuation_byte() {
+    // A 2-byte sequence whose second byte isn't a 0x80..=0xBF continuation.
+    let _ = copstr::Str::<2>::new_const_checked_u8(&[0xC2, 0x00]);
+}
+
+/* serde tests */
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_roundtrip() -> Result<()> {
+    let basic = Str::new("basic")?;
+    let json = serde_json::to_string(&basic)?;
+    assert_eq!(json, r#""basic""#);
+    let back: Str = serde_json::from_str(&json)?;
+    assert_eq!(back, basic);
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_overflow() -> Result<()> {
+    let result: Result<Str, _> = serde_json::from_str(r#""stringification""#);
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_padded_array_roundtrip() -> Result<()> {
+    let basic = Str::new("bas")?;
+    assert_eq!(basic.to_padded_array(), [b'b', b'a', b's', 0, 0]);
+    let back = Str::from_padded_array(basic.to_padded_array())?;
+    assert_eq!(back, basic);
+    Ok(())
+}
+
+#[test]
+fn test_padded_array_after_shrink() -> Result<()> {
+    let mut basic = Str::new("hello")?;
+    basic.replace("a")?;
+    assert_eq!(basic.to_padded_array(), [b'a', 0, 0, 0, 0]);
+    let back = Str::from_padded_array(basic.to_padded_array())?;
+    assert_str(&back, "a");
+    Ok(())
+}
+
+#[test]
+fn test_padded_array_invalid_utf8() -> Result<()> {
+    let mut arr = [0u8; 5];
+    arr[0..4].copy_from_slice(&INVALID_UTF8);
+    let result = Str::from_padded_array(arr);
+    assert_matches!(result, Err(copstr::Error::Utf8(_)));
+    Ok(())
+}
+
+#[test]
+fn test_padded_array_trailing_nul_is_lost() -> Result<()> {
+    // Documents the known caveat: content ending in '\0' is
+    // indistinguishable from padding and doesn't round-trip.
+    let basic = Str::new("a\0")?;
+    let back = Str::from_padded_array(basic.to_padded_array())?;
+    assert_str(&back, "a");
+    Ok(())
+}